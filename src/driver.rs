@@ -44,5 +44,12 @@ pub fn stable_mir_driver(args_outer: &[String], callback_fn: fn(TyCtxt) -> ()) {
     let early_dcx =
         rustc_session::EarlyDiagCtxt::new(rustc_session::config::ErrorOutputType::default());
     rustc_driver::init_rustc_env_logger(&early_dcx);
-    let _ = rustc_driver::RunCompiler::new(args_outer, &mut callbacks).run();
+    // fatal errors (e.g. invalid `--reachable-from` roots) have already been
+    // reported by the compiler, only the exit status is left to set
+    let result = rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::RunCompiler::new(args_outer, &mut callbacks).run()
+    });
+    if result.is_err() {
+        std::process::exit(1);
+    }
 }
@@ -1,14 +1,30 @@
 #![feature(rustc_private)]
-use std::env;
-pub mod driver;
-pub mod printer;
-use driver::stable_mir_driver;
-use printer::emit_smir;
+use stable_mir_json::driver::stable_mir_driver;
 use stable_mir_json::mk_graph::{emit_d2file, emit_dotfile};
+use stable_mir_json::printer::{emit_smir, enable_raw_mir, set_reachable_roots};
+use std::env;
 
 fn main() {
     let mut args: Vec<String> = env::args().collect();
 
+    // `--reachable-from <symbol>` may be given several times and anywhere on the command line
+    let mut roots = vec![];
+    while let Some(pos) = args.iter().position(|a| a == "--reachable-from") {
+        if args.get(pos + 1).is_none_or(|a| a.starts_with('-')) {
+            eprintln!("error: --reachable-from requires a symbol argument");
+            std::process::exit(1);
+        }
+        roots.push(args.remove(pos + 1));
+        args.remove(pos);
+    }
+    if !roots.is_empty() {
+        set_reachable_roots(roots);
+    }
+    if let Some(pos) = args.iter().position(|a| a == "--raw-mir") {
+        args.remove(pos);
+        enable_raw_mir();
+    }
+
     match args.get(1) {
         None => stable_mir_driver(&args, emit_smir), // backward compatibility
         Some(arg) if arg == "--json" => {
@@ -4,15 +4,18 @@ use std::collections::HashMap;
 
 extern crate stable_mir;
 use stable_mir::mir::{
-    BorrowKind, ConstOperand, Mutability, NonDivergingIntrinsic, Operand, Rvalue, Statement,
-    StatementKind, Terminator, TerminatorKind,
+    AggregateKind, BorrowKind, CastKind, ConstOperand, Mutability, NonDivergingIntrinsic, Operand,
+    Rvalue, Statement, StatementKind, Terminator, TerminatorKind,
 };
 use stable_mir::ty::{ConstantKind, IndexedVal, MirConst, Ty};
 
 use crate::printer::SmirJson;
 
 use super::index::{AllocIndex, LayoutInfo, TypeEntry, TypeIndex, TypeKind};
-use super::util::{function_string, short_fn_name, GraphLabelString};
+use super::util::{
+    aggregate_label, cast_note, coverage_label, function_string, short_fn_name, shorten_type_name,
+    GraphLabelString, TYPE_NAME_WIDTH,
+};
 
 // =============================================================================
 // GraphContext
@@ -45,7 +48,7 @@ impl GraphContext {
     /// Render a constant operand with alloc information
     pub fn render_const(&self, const_: &MirConst) -> String {
         let ty = const_.ty();
-        let ty_name = self.types.get_name(ty);
+        let ty_name = self.short_type_name(ty);
 
         match const_.kind() {
             ConstantKind::Allocated(alloc) => {
@@ -151,7 +154,7 @@ impl GraphContext {
                 projections,
                 variance: _,
             } => format!("Ascribe {}.{}", place.label(), projections.base),
-            Coverage(_) => "Coverage".to_string(),
+            Coverage(kind) => coverage_label(&kind.to_string()),
             Intrinsic(intr) => format!("Intr: {}", self.render_intrinsic(intr)),
             ConstEvalCounter {} => "ConstEvalCounter".to_string(),
             Nop {} => "Nop".to_string(),
@@ -166,17 +169,35 @@ impl GraphContext {
                 Mutability::Not => format!("&raw {}", p.label()),
                 Mutability::Mut => format!("&raw mut {}", p.label()),
             },
-            Aggregate(kind, operands) => {
-                let os: Vec<String> = operands.iter().map(|op| self.render_operand(op)).collect();
-                format!("{} ({})", kind.label(), os.join(", "))
-            }
+            Aggregate(kind, operands) => self.render_aggregate(kind, operands),
             BinaryOp(binop, op1, op2) => format!(
                 "{:?}({}, {})",
                 binop,
                 self.render_operand(op1),
                 self.render_operand(op2)
             ),
-            Cast(kind, op, _ty) => format!("Cast-{:?} {}", kind, self.render_operand(op)),
+            Cast(kind, op, ty) => {
+                let note = match kind {
+                    // primitives are not in the type index, ask the compiler for their size
+                    CastKind::Transmute => self
+                        .types
+                        .get_layout(*ty)
+                        .map(|layout| layout.size)
+                        .or_else(|| ty.layout().ok().map(|l| l.shape().size.bytes()))
+                        .map(|size| format!("reinterprets {} bytes", size)),
+                    _ => cast_note(kind, *ty),
+                };
+                let kind_str = match note {
+                    Some(note) => format!("{}: {}", kind.label(), note),
+                    None => kind.label(),
+                };
+                format!(
+                    "{} as {} ({})",
+                    self.render_operand(op),
+                    self.short_type_name(*ty),
+                    kind_str
+                )
+            }
             CheckedBinaryOp(binop, op1, op2) => {
                 format!(
                     "chkd-{:?}({}, {})",
@@ -207,6 +228,26 @@ impl GraphContext {
         }
     }
 
+    /// Render an aggregate in constructor syntax (see `aggregate_label`). ADTs
+    /// with a known layout also mention the constructed type and its size.
+    pub fn render_aggregate(&self, kind: &AggregateKind, operands: &[Operand]) -> String {
+        let os: Vec<String> = operands.iter().map(|op| self.render_operand(op)).collect();
+        let label = aggregate_label(kind, &os);
+        let AggregateKind::Adt(adt, _, args, _, _) = kind else {
+            return label;
+        };
+        let ty = adt.ty_with_args(args);
+        match self.types.get_layout(ty) {
+            Some(layout) => format!(
+                "{} (constructs {}, {} bytes)",
+                label,
+                self.short_type_name(ty),
+                layout.size
+            ),
+            None => label,
+        }
+    }
+
     /// Render intrinsic with context
     pub fn render_intrinsic(&self, intr: &NonDivergingIntrinsic) -> String {
         use NonDivergingIntrinsic::*;
@@ -270,9 +311,14 @@ impl GraphContext {
         self.types.get_layout(ty)
     }
 
+    /// Get a type's name shortened for display (see `shorten_type_name`)
+    pub fn short_type_name(&self, ty: Ty) -> String {
+        shorten_type_name(&self.types.get_name(ty), TYPE_NAME_WIDTH)
+    }
+
     /// Render a type with its size and alignment
     pub fn render_type_with_layout(&self, ty: Ty) -> String {
-        let name = self.types.get_name(ty);
+        let name = self.short_type_name(ty);
         match self.types.get_layout(ty) {
             Some(layout) => format!("{} ({} bytes, align {})", name, layout.size, layout.align),
             None => name,
@@ -370,15 +416,13 @@ impl GraphContext {
         lines
     }
 
-    /// Generate the types legend as lines for display (types with layout info)
-    pub fn types_legend_lines(&self) -> Vec<String> {
-        let mut lines = vec!["TYPES".to_string()];
-
-        // Collect and sort types that have interesting layout info
-        let mut entries: Vec<_> = self
+    /// Composite types shown in the types legend, sorted by name
+    fn legend_types(&self) -> Vec<&TypeEntry> {
+        let mut entries: Vec<&TypeEntry> = self
             .types
             .iter()
-            .filter(|(_, entry)| {
+            .map(|(_, entry)| entry)
+            .filter(|entry| {
                 // Only include composite types with layout
                 matches!(
                     entry.kind,
@@ -389,17 +433,47 @@ impl GraphContext {
                 )
             })
             .collect();
-        entries.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+
+    /// Generate the types legend as lines for display (types with layout info)
+    pub fn types_legend_lines(&self) -> Vec<String> {
+        let mut lines = vec!["TYPES".to_string()];
+        let entries = self.legend_types();
+
+        // shortened names are only used where they stay unique, the legend is
+        // there to tell types apart
+        let mut short_counts: HashMap<String, usize> = HashMap::new();
+        for entry in &entries {
+            *short_counts
+                .entry(shorten_type_name(&entry.name, TYPE_NAME_WIDTH))
+                .or_default() += 1;
+        }
 
-        for (_ty_id, entry) in entries {
+        for entry in entries {
             let layout_str = entry
                 .layout
                 .as_ref()
                 .map(|l| format!(" ({} bytes)", l.size))
                 .unwrap_or_default();
-            lines.push(format!("{}{}", entry.name, layout_str));
+            let short = shorten_type_name(&entry.name, TYPE_NAME_WIDTH);
+            let name = if short_counts[&short] > 1 {
+                &entry.name
+            } else {
+                &short
+            };
+            lines.push(format!("{}{}", name, layout_str));
         }
 
         lines
     }
+
+    /// Full type names of the types legend, in legend order
+    pub fn types_legend_full_names(&self) -> Vec<String> {
+        self.legend_types()
+            .into_iter()
+            .map(|entry| entry.name.clone())
+            .collect()
+    }
 }
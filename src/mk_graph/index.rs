@@ -5,7 +5,7 @@ use std::collections::HashMap;
 extern crate stable_mir;
 use stable_mir::abi::{FieldsShape, LayoutShape};
 use stable_mir::mir::alloc::GlobalAlloc;
-use stable_mir::ty::{IndexedVal, Ty};
+use stable_mir::ty::{IndexedVal, RigidTy, Ty};
 use stable_mir::CrateDef;
 
 use crate::printer::{AllocInfo, TypeMetadata};
@@ -267,11 +267,27 @@ impl TypeIndex {
 // TypeEntry Implementation
 // =============================================================================
 
+/// Rust spelling of a primitive type (`u64`, `bool`), falling back to the
+/// `RigidTy` debug form for anything else
+fn primitive_name(rigid: &RigidTy) -> String {
+    match rigid {
+        RigidTy::Bool => "bool".to_string(),
+        RigidTy::Char => "char".to_string(),
+        RigidTy::Str => "str".to_string(),
+        RigidTy::Never => "!".to_string(),
+        // `I32`, `Usize`, `F64` etc. lowercase to their Rust names
+        RigidTy::Int(t) => format!("{:?}", t).to_lowercase(),
+        RigidTy::Uint(t) => format!("{:?}", t).to_lowercase(),
+        RigidTy::Float(t) => format!("{:?}", t).to_lowercase(),
+        other => format!("{:?}", other),
+    }
+}
+
 impl TypeEntry {
     pub fn from_metadata(metadata: &TypeMetadata, ty: Ty) -> Self {
         let (name, kind, layout) = match metadata {
             TypeMetadata::PrimitiveType(rigid) => {
-                (format!("{:?}", rigid), TypeKind::Primitive, None)
+                (primitive_name(rigid), TypeKind::Primitive, None)
             }
             TypeMetadata::StructType {
                 name,
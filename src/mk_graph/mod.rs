@@ -3,16 +3,10 @@
 //! This module provides functionality to generate graph visualizations
 //! of Rust's MIR in various formats (DOT, D2).
 
-use std::fs::File;
-use std::io::{self, Write};
-
 extern crate rustc_middle;
 use rustc_middle::ty::TyCtxt;
 
-extern crate rustc_session;
-use rustc_session::config::{OutFileName, OutputType};
-
-use crate::printer::collect_smir;
+use crate::printer::{collect_smir, write_output};
 
 // Sub-modules
 pub mod context;
@@ -32,37 +26,11 @@ pub use util::GraphLabelString;
 /// Entry point to write the DOT file
 pub fn emit_dotfile(tcx: TyCtxt<'_>) {
     let smir_dot = collect_smir(tcx).to_dot_file();
-
-    match tcx.output_filenames(()).path(OutputType::Mir) {
-        OutFileName::Stdout => {
-            write!(io::stdout(), "{}", smir_dot).expect("Failed to write smir.dot");
-        }
-        OutFileName::Real(path) => {
-            let out_path = path.with_extension("smir.dot");
-            let mut b = io::BufWriter::new(
-                File::create(&out_path)
-                    .unwrap_or_else(|e| panic!("Failed to create {}: {}", out_path.display(), e)),
-            );
-            write!(b, "{}", smir_dot).expect("Failed to write smir.dot");
-        }
-    }
+    write_output(tcx, "smir.dot", &smir_dot);
 }
 
 /// Entry point to write the D2 file
 pub fn emit_d2file(tcx: TyCtxt<'_>) {
     let smir_d2 = collect_smir(tcx).to_d2_file();
-
-    match tcx.output_filenames(()).path(OutputType::Mir) {
-        OutFileName::Stdout => {
-            write!(io::stdout(), "{}", smir_d2).expect("Failed to write smir.d2");
-        }
-        OutFileName::Real(path) => {
-            let out_path = path.with_extension("smir.d2");
-            let mut b = io::BufWriter::new(
-                File::create(&out_path)
-                    .unwrap_or_else(|e| panic!("Failed to create {}: {}", out_path.display(), e)),
-            );
-            write!(b, "{}", smir_d2).expect("Failed to write smir.d2");
-        }
-    }
+    write_output(tcx, "smir.d2", &smir_d2);
 }
@@ -8,7 +8,8 @@ use crate::MonoItemKind;
 
 use crate::mk_graph::context::GraphContext;
 use crate::mk_graph::util::{
-    escape_d2, is_unqualified, name_lines, short_name, terminator_targets,
+    compute_predecessors, escape_d2, is_unqualified, is_unreachable_otherwise, name_lines,
+    preds_label, short_name, terminator_targets,
 };
 
 impl SmirJson<'_> {
@@ -88,6 +89,7 @@ fn render_d2_function(
 }
 
 fn render_d2_blocks(body: &stable_mir::mir::Body, ctx: &GraphContext, out: &mut String) {
+    let preds = compute_predecessors(&body.blocks);
     for (idx, block) in body.blocks.iter().enumerate() {
         let stmts: Vec<String> = block
             .statements
@@ -97,6 +99,9 @@ fn render_d2_blocks(body: &stable_mir::mir::Body, ctx: &GraphContext, out: &mut
         let term_str = escape_d2(&ctx.render_terminator(&block.terminator));
 
         let mut label = format!("bb{}:", idx);
+        if let Some(preds) = preds_label(&preds[idx]) {
+            label.push_str(&format!("\\n{}", preds));
+        }
         for stmt in &stmts {
             label.push_str(&format!("\\n{}", stmt));
         }
@@ -108,8 +113,24 @@ fn render_d2_blocks(body: &stable_mir::mir::Body, ctx: &GraphContext, out: &mut
 
 fn render_d2_block_edges(body: &stable_mir::mir::Body, out: &mut String) {
     for (idx, block) in body.blocks.iter().enumerate() {
+        // exhaustive matches: grey out the otherwise arm into a bare Unreachable block
+        let faded = match &block.terminator.kind {
+            TerminatorKind::SwitchInt { targets, .. }
+                if is_unreachable_otherwise(&body.blocks, targets) =>
+            {
+                Some(targets.otherwise())
+            }
+            _ => None,
+        };
         for target in terminator_targets(&block.terminator) {
-            out.push_str(&format!("  bb{} -> bb{}\n", idx, target));
+            if faded == Some(target) {
+                out.push_str(&format!(
+                    "  bb{} -> bb{}: {{style.stroke: \"#bbbbbb\"; style.stroke-dash: 3}}\n",
+                    idx, target
+                ));
+            } else {
+                out.push_str(&format!("  bb{} -> bb{}\n", idx, target));
+            }
         }
     }
 }
@@ -5,13 +5,18 @@ use std::collections::HashSet;
 use dot_writer::{Attributes, Color, DotWriter, Scope, Shape, Style};
 
 extern crate stable_mir;
-use stable_mir::mir::{BasicBlock, ConstOperand, Operand, TerminatorKind, UnwindAction};
+use stable_mir::mir::{BasicBlock, ConstOperand, LocalDecl, Operand, TerminatorKind, UnwindAction};
+use stable_mir::ty::{RigidTy, TyKind};
 
 use crate::printer::SmirJson;
 use crate::MonoItemKind;
 
 use crate::mk_graph::context::GraphContext;
-use crate::mk_graph::util::{block_name, is_unqualified, name_lines, short_name, GraphLabelString};
+use crate::mk_graph::util::{
+    block_name, compute_predecessors, escape_dot, is_unqualified, is_unreachable_otherwise,
+    name_lines, preds_label, short_name, switch_branch_labels, switch_value_label,
+    GraphLabelString,
+};
 
 impl SmirJson<'_> {
     /// Convert the MIR to DOT (Graphviz) format
@@ -51,6 +56,8 @@ impl SmirJson<'_> {
                 let mut lines = type_lines;
                 lines.push("".to_string());
                 type_node.set_label(&lines.join("\\l"));
+                // full type names on hover, the label may shorten them
+                type_node.set("tooltip", &ctx.types_legend_full_names().join("\\n"), true);
                 type_node.set_style(Style::Filled);
                 type_node.set("color", "lavender", false);
             }
@@ -91,14 +98,19 @@ impl SmirJson<'_> {
                         local_node.set("color", "palegreen3", false);
                         drop(local_node);
 
+                        let blocks: &[BasicBlock] = body.as_ref().map_or(&[], |b| &b.blocks);
+                        let locals: &[LocalDecl] = body.as_ref().map_or(&[], |b| b.locals());
+                        let preds = compute_predecessors(blocks);
+
                         // Cannot define local functions that capture env. variables. Instead we define _closures_.
                         let process_block =
                             |cluster: &mut Scope<'_, '_>, node_id: usize, b: &BasicBlock| {
                                 let name = &item.symbol_name;
                                 let this_block = block_name(name, node_id);
 
-                                let mut label_strs: Vec<String> =
-                                    b.statements.iter().map(|s| ctx.render_stmt(s)).collect();
+                                let mut label_strs: Vec<String> = vec![format!("bb{}:", node_id)];
+                                label_strs.extend(preds_label(&preds[node_id]));
+                                label_strs.extend(b.statements.iter().map(|s| ctx.render_stmt(s)));
 
                                 use TerminatorKind::*;
                                 match &b.terminator.kind {
@@ -111,19 +123,29 @@ impl SmirJson<'_> {
                                             "SwitchInt {}",
                                             ctx.render_operand(discr)
                                         ));
-                                        for (d, t) in targets.clone().branches() {
+                                        let is_char = discr.ty(locals).is_ok_and(|ty| {
+                                            matches!(ty.kind(), TyKind::RigidTy(RigidTy::Char))
+                                        });
+                                        for (t, label) in
+                                            switch_branch_labels(targets.branches(), |v| {
+                                                escape_dot(&switch_value_label(v, is_char))
+                                            })
+                                        {
                                             cluster
                                                 .edge(&this_block, block_name(name, t))
                                                 .attributes()
-                                                .set_label(&format!("{d}"));
+                                                .set_label(&label);
+                                        }
+                                        let other = cluster.edge(
+                                            &this_block,
+                                            block_name(name, targets.otherwise()),
+                                        );
+                                        let mut attrs = other.attributes();
+                                        attrs.set_label("other");
+                                        // exhaustive matches: the otherwise arm carries no information
+                                        if is_unreachable_otherwise(blocks, targets) {
+                                            attrs.set_color(Color::Grey).set_style(Style::Dashed);
                                         }
-                                        cluster
-                                            .edge(
-                                                &this_block,
-                                                block_name(name, targets.otherwise()),
-                                            )
-                                            .attributes()
-                                            .set_label("other");
                                     }
                                     Resume {} => {
                                         label_strs.push("Resume".to_string());
@@ -4,10 +4,12 @@ use std::hash::{DefaultHasher, Hash, Hasher};
 
 extern crate stable_mir;
 use stable_mir::mir::{
-    AggregateKind, BorrowKind, ConstOperand, Mutability, NonDivergingIntrinsic, NullOp, Operand,
-    Place, ProjectionElem, Rvalue, Terminator, TerminatorKind, UnwindAction,
+    AggregateKind, BasicBlock, BorrowKind, CastKind, ConstOperand, Mutability,
+    NonDivergingIntrinsic, NullOp, Operand, Place, PointerCoercion, ProjectionElem, Rvalue,
+    SwitchTargets, Terminator, TerminatorKind, UnwindAction,
 };
-use stable_mir::ty::{IndexedVal, RigidTy};
+use stable_mir::ty::{AdtKind, IndexedVal, RigidTy, Ty, TyKind};
+use stable_mir::CrateDef;
 
 use crate::printer::FnSymType;
 
@@ -70,10 +72,13 @@ impl GraphLabelString for Rvalue {
             },
             Aggregate(kind, operands) => {
                 let os: Vec<String> = operands.iter().map(|op| op.label()).collect();
-                format!("{} ({})", kind.label(), os.join(", "))
+                aggregate_label(kind, &os)
             }
             BinaryOp(binop, op1, op2) => format!("{:?}({}, {})", binop, op1.label(), op2.label()),
-            Cast(kind, op, _ty) => format!("Cast-{:?} {}", kind, op.label()),
+            Cast(kind, op, ty) => match cast_note(kind, *ty) {
+                Some(note) => format!("{} as {} ({}: {})", op.label(), ty, kind.label(), note),
+                None => format!("{} as {} ({})", op.label(), ty, kind.label()),
+            },
             CheckedBinaryOp(binop, op1, op2) => {
                 format!("chkd-{:?}({}, {})", binop, op1.label(), op2.label())
             }
@@ -100,6 +105,38 @@ impl GraphLabelString for Rvalue {
     }
 }
 
+impl GraphLabelString for CastKind {
+    fn label(&self) -> String {
+        use CastKind::*;
+        match &self {
+            PointerExposeAddress => "ExposeProvenance".to_string(),
+            PointerWithExposedProvenance => "WithExposedProvenance".to_string(),
+            PointerCoercion(coercion) => format!("{:?}", coercion),
+            other => format!("{:?}", other),
+        }
+    }
+}
+
+/// Explain what a cast does beyond converting the value, for the kinds where
+/// that is not obvious from the kind name. Transmutes are annotated by the
+/// caller, which knows their size.
+pub fn cast_note(kind: &CastKind, target: Ty) -> Option<String> {
+    match kind {
+        CastKind::PointerCoercion(PointerCoercion::Unsize) => {
+            let pointee = target.kind().builtin_deref(true).map(|t| t.ty.kind());
+            let metadata = match pointee {
+                Some(TyKind::RigidTy(RigidTy::Dynamic(..))) => ", adds vtable",
+                Some(TyKind::RigidTy(RigidTy::Slice(_) | RigidTy::Str)) => ", adds len",
+                _ => "",
+            };
+            Some(format!("creates fat pointer{}", metadata))
+        }
+        CastKind::PointerExposeAddress => Some("exposes provenance".to_string()),
+        CastKind::PointerWithExposedProvenance => Some("creates provenance".to_string()),
+        _ => None,
+    }
+}
+
 impl GraphLabelString for NullOp {
     fn label(&self) -> String {
         match &self {
@@ -124,6 +161,72 @@ impl GraphLabelString for NonDivergingIntrinsic {
     }
 }
 
+/// Render a coverage statement. Stable MIR only carries the compiler's debug
+/// text for the coverage kind (`CounterIncrement(3)`), the common kinds are
+/// parsed back out of it; anything else is shown as is.
+pub fn coverage_label(kind: &str) -> String {
+    let numbered = |prefix: &str| {
+        kind.strip_prefix(prefix)?
+            .strip_prefix('(')?
+            .strip_suffix(')')?
+            .parse::<u32>()
+            .ok()
+    };
+    if let Some(n) = numbered("CounterIncrement") {
+        format!("Coverage counter #{}", n)
+    } else if let Some(n) = numbered("ExpressionUsed") {
+        format!("Coverage expression #{}", n)
+    } else if let Some(n) = numbered("BlockMarker") {
+        format!("Coverage block marker #{}", n)
+    } else {
+        format!("Coverage {}", kind)
+    }
+}
+
+/// Render an aggregate with already rendered operands in constructor syntax,
+/// naming the type or variant and, where the definition has them, the fields
+/// fed by each operand: `S { a: x }`, `Some(x)`, `(x, y)`, `[x, y]`
+pub fn aggregate_label(kind: &AggregateKind, os: &[String]) -> String {
+    match kind {
+        AggregateKind::Adt(adt, idx, _, _, active_field) => {
+            let Some(variant) = adt.variant(*idx) else {
+                return format!("{} ({})", kind.label(), os.join(", "));
+            };
+            let head = match adt.kind() {
+                AdtKind::Enum => variant.name(),
+                AdtKind::Struct | AdtKind::Union => adt.trimmed_name(),
+            };
+            let fields = variant.fields();
+            // unions initialise exactly one (the active) field
+            let names: Vec<&str> = match active_field {
+                Some(f) => fields
+                    .get(*f)
+                    .map(|f| f.name.as_str())
+                    .into_iter()
+                    .collect(),
+                None => fields.iter().map(|f| f.name.as_str()).collect(),
+            };
+            let is_positional = names.iter().all(|n| n.parse::<usize>().is_ok());
+            if os.is_empty() {
+                head
+            } else if is_positional || names.len() != os.len() {
+                format!("{}({})", head, os.join(", "))
+            } else {
+                let fs: Vec<String> = names
+                    .iter()
+                    .zip(os.iter())
+                    .map(|(n, o)| format!("{}: {}", n, o))
+                    .collect();
+                format!("{} {{ {} }}", head, fs.join(", "))
+            }
+        }
+        AggregateKind::Tuple {} if os.len() == 1 => format!("({},)", os[0]),
+        AggregateKind::Tuple {} => format!("({})", os.join(", ")),
+        AggregateKind::Array(_) => format!("[{}]", os.join(", ")),
+        _ => format!("{} ({})", kind.label(), os.join(", ")),
+    }
+}
+
 // =============================================================================
 // Projection Helpers
 // =============================================================================
@@ -188,6 +291,75 @@ pub fn name_lines(name: &str) -> String {
         .join("\\n")
 }
 
+/// Default display width for type names in graph labels
+pub const TYPE_NAME_WIDTH: usize = 60;
+
+/// Crate roots whose paths are shortened to their last segment
+const STD_ROOTS: [&str; 3] = ["std", "core", "alloc"];
+
+/// Generic arguments that are defaults and add nothing to a rendered type
+const DEFAULT_GENERIC_ARGS: [&str; 2] = ["Global", "RandomState"];
+
+/// Shorten a rendered type name for display. Paths into `std`, `core` and
+/// `alloc` are reduced to their last segment, default generic arguments
+/// (allocator, hasher) are dropped, and anything longer than `max_width`
+/// characters is ellipsized in the middle.
+pub fn shorten_type_name(name: &str, max_width: usize) -> String {
+    // abbreviate std paths, one path token at a time
+    let mut short = String::with_capacity(name.len());
+    let mut token = String::new();
+    let flush = |token: &mut String, short: &mut String| {
+        // a turbofish (`identity::<u32>`) leaves the token ending in `::`
+        let (path, turbofish) = match token.strip_suffix("::") {
+            Some(path) => (path, "::"),
+            None => (token.as_str(), ""),
+        };
+        match path.split("::").next() {
+            Some(root) if STD_ROOTS.contains(&root) && path.contains("::") => {
+                short.push_str(path.rsplit("::").next().unwrap_or(path));
+                short.push_str(turbofish);
+            }
+            _ => short.push_str(token),
+        }
+        token.clear();
+    };
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            token.push(c);
+        } else {
+            flush(&mut token, &mut short);
+            short.push(c);
+        }
+    }
+    flush(&mut token, &mut short);
+
+    // drop default generic arguments
+    for arg in DEFAULT_GENERIC_ARGS {
+        short = short
+            .replace(&format!(", {}>", arg), ">")
+            .replace(&format!("<{}>", arg), "");
+    }
+
+    middle_ellipsis(&short, max_width)
+}
+
+/// Truncate a string to at most `max_width` characters by replacing its middle
+/// with an ellipsis
+pub fn middle_ellipsis(s: &str, max_width: usize) -> String {
+    let len = s.chars().count();
+    if len <= max_width {
+        return s.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    let tail = (max_width - 1) / 2;
+    let head = max_width - 1 - tail;
+    let start: String = s.chars().take(head).collect();
+    let end: String = s.chars().skip(len - tail).collect();
+    format!("{}…{}", start, end)
+}
+
 /// Generate a consistent short name (hash-based) for a function
 pub fn short_name(function_name: &str) -> String {
     let mut h = DefaultHasher::new();
@@ -206,6 +378,12 @@ pub fn block_name(function_name: &str, id: usize) -> String {
 // Escape Helpers
 // =============================================================================
 
+/// Escape special characters for quoted DOT attribute values. Only for text
+/// that is not meant to contain DOT escapes such as `\l`.
+pub fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 /// Escape special characters for D2 string labels
 pub fn escape_d2(s: &str) -> String {
     s.replace('\\', "\\\\")
@@ -280,3 +458,357 @@ pub fn terminator_targets(term: &Terminator) -> Vec<usize> {
         }
     }
 }
+
+/// Compute the predecessor blocks of every block of a body, in one pass over
+/// the terminators. Each list is sorted and free of duplicates.
+pub fn compute_predecessors(blocks: &[BasicBlock]) -> Vec<Vec<usize>> {
+    let mut preds: Vec<Vec<usize>> = vec![vec![]; blocks.len()];
+    for (idx, block) in blocks.iter().enumerate() {
+        for target in terminator_targets(&block.terminator) {
+            if let Some(p) = preds.get_mut(target) {
+                if p.last() != Some(&idx) {
+                    p.push(idx);
+                }
+            }
+        }
+    }
+    preds
+}
+
+/// Render a block's predecessor list for its label, `None` for entry blocks
+pub fn preds_label(preds: &[usize]) -> Option<String> {
+    if preds.is_empty() {
+        return None;
+    }
+    let names: Vec<String> = preds.iter().map(|p| format!("bb{}", p)).collect();
+    Some(format!("preds: {}", names.join(", ")))
+}
+
+/// Check whether a block consists of nothing but an `Unreachable` terminator
+pub fn is_bare_unreachable(block: &BasicBlock) -> bool {
+    block.statements.is_empty() && matches!(block.terminator.kind, TerminatorKind::Unreachable)
+}
+
+/// Check whether the `otherwise` arm of a switch leads straight to a bare
+/// `Unreachable` block, as rustc emits for exhaustive matches
+pub fn is_unreachable_otherwise(blocks: &[BasicBlock], targets: &SwitchTargets) -> bool {
+    blocks
+        .get(targets.otherwise())
+        .is_some_and(is_bare_unreachable)
+}
+
+/// Group the branches of a `SwitchInt` by target block, one label per target.
+/// Runs of three or more consecutive values are shown as a range (`97–122`),
+/// anything else as a comma-separated list. `render` formats a single value.
+/// Targets keep the order in which they first appear among the branches.
+pub fn switch_branch_labels(
+    branches: impl Iterator<Item = (u128, usize)>,
+    render: impl Fn(u128) -> String,
+) -> Vec<(usize, String)> {
+    let mut by_target: Vec<(usize, Vec<u128>)> = vec![];
+    for (value, target) in branches {
+        match by_target.iter_mut().find(|(t, _)| *t == target) {
+            Some((_, values)) => values.push(value),
+            None => by_target.push((target, vec![value])),
+        }
+    }
+
+    by_target
+        .into_iter()
+        .map(|(target, mut values)| {
+            values.sort_unstable();
+            let mut parts: Vec<String> = vec![];
+            let mut i = 0;
+            while i < values.len() {
+                // extend the run while the next value is exactly one more (no wraparound)
+                let mut j = i;
+                while j + 1 < values.len() && values[j].checked_add(1) == Some(values[j + 1]) {
+                    j += 1;
+                }
+                if j - i >= 2 {
+                    parts.push(format!("{}–{}", render(values[i]), render(values[j])));
+                } else {
+                    parts.extend(values[i..=j].iter().map(|&v| render(v)));
+                }
+                i = j + 1;
+            }
+            (target, parts.join(", "))
+        })
+        .collect()
+}
+
+/// Render a switch value, decoding it as a character literal for `char` switches
+pub fn switch_value_label(value: u128, is_char: bool) -> String {
+    match u32::try_from(value).ok().and_then(char::from_u32) {
+        Some(c) if is_char => format!("{:?}", c),
+        _ => format!("{value}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shorten_type_name_table() {
+        let cases: &[(&str, usize, &str)] = &[
+            // default generic arguments, nested
+            (
+                "std::vec::Vec<std::vec::Vec<u8, std::alloc::Global>, std::alloc::Global>",
+                60,
+                "Vec<Vec<u8>>",
+            ),
+            (
+                "std::collections::HashMap<std::string::String, u32, std::hash::RandomState>",
+                60,
+                "HashMap<String, u32>",
+            ),
+            (
+                "alloc::boxed::Box<[u8], alloc::alloc::Global>",
+                60,
+                "Box<[u8]>",
+            ),
+            // qualified paths keep their associated item
+            ("<T as std::ops::Add>::Output", 60, "<T as Add>::Output"),
+            (
+                "<u8 as core::ops::Add<u8>>::Output",
+                60,
+                "<u8 as Add<u8>>::Output",
+            ),
+            // closures keep their source location, std paths inside are shortened
+            (
+                "{closure@src/main.rs:3:13: 3:16}",
+                60,
+                "{closure@src/main.rs:3:13: 3:16}",
+            ),
+            (
+                "{closure@std::rt::lang_start<()>::{closure#0}}",
+                60,
+                "{closure@lang_start<()>::{closure#0}}",
+            ),
+            // fn items and turbofish paths keep the function name
+            (
+                "fn(u32) -> u32 {std::convert::identity::<u32>}",
+                60,
+                "fn(u32) -> u32 {identity::<u32>}",
+            ),
+            (
+                "fn() -> std::vec::Vec<u8, std::alloc::Global> {std::vec::Vec::<u8>::new}",
+                60,
+                "fn() -> Vec<u8> {Vec::<u8>::new}",
+            ),
+            (
+                "core::mem::size_of::<std::string::String>",
+                60,
+                "size_of::<String>",
+            ),
+            ("my_crate::helper::<u8>", 60, "my_crate::helper::<u8>"),
+            // user crates and crate-local paths are left alone
+            ("my_crate::std::Thing", 60, "my_crate::std::Thing"),
+            ("stdx::Thing", 60, "stdx::Thing"),
+            ("Global", 60, "Global"),
+            // non-ASCII identifiers and width limits count characters
+            ("größe::Maß<ü8>", 60, "größe::Maß<ü8>"),
+            ("größe::Maß<ü8>", 5, "gr…8>"),
+            (
+                "std::result::Result<std::string::String, std::io::Error>",
+                10,
+                "Resul…ror>",
+            ),
+        ];
+        for (input, width, expected) in cases {
+            assert_eq!(
+                shorten_type_name(input, *width),
+                *expected,
+                "shorten_type_name({input:?}, {width})"
+            );
+        }
+    }
+
+    #[test]
+    fn middle_ellipsis_widths() {
+        let cases: &[(&str, usize, &str)] = &[
+            ("abcdef", 6, "abcdef"),
+            ("abcdef", 7, "abcdef"),
+            ("abcdef", 5, "ab…ef"),
+            ("abcdef", 4, "ab…f"),
+            ("abcdef", 3, "a…f"),
+            ("abcdef", 2, "a…"),
+            ("abcdef", 1, "…"),
+            ("abcdef", 0, ""),
+            ("", 0, ""),
+            ("äöüß", 4, "äöüß"),
+            ("äöüßé", 4, "äö…é"),
+            ("日本語のテキスト", 5, "日本…スト"),
+        ];
+        for (input, width, expected) in cases {
+            let out = middle_ellipsis(input, *width);
+            assert_eq!(out, *expected, "middle_ellipsis({input:?}, {width})");
+            assert!(out.chars().count() <= *width || out == *input);
+        }
+    }
+
+    fn labels(branches: &[(u128, usize)]) -> Vec<(usize, String)> {
+        switch_branch_labels(branches.iter().copied(), |v| v.to_string())
+    }
+
+    #[test]
+    fn switch_branch_labels_ranges() {
+        // three or more consecutive values become a range
+        assert_eq!(
+            labels(&[(1, 5), (2, 5), (3, 5), (4, 5)]),
+            vec![(5, "1–4".to_string())]
+        );
+        // two consecutive values stay a list
+        assert_eq!(labels(&[(1, 5), (2, 5)]), vec![(5, "1, 2".to_string())]);
+        // non-consecutive values to one target, unsorted input
+        assert_eq!(
+            labels(&[(9, 5), (1, 5), (4, 5)]),
+            vec![(5, "1, 4, 9".to_string())]
+        );
+        // a run and stragglers in the same label
+        assert_eq!(
+            labels(&[(0, 1), (10, 1), (11, 1), (12, 1), (20, 1)]),
+            vec![(1, "0, 10–12, 20".to_string())]
+        );
+    }
+
+    #[test]
+    fn switch_branch_labels_type_bounds() {
+        assert_eq!(
+            labels(&[(u128::MAX - 2, 3), (u128::MAX - 1, 3), (u128::MAX, 3)]),
+            vec![(3, format!("{}–{}", u128::MAX - 2, u128::MAX))]
+        );
+        // no wraparound from MAX to 0
+        assert_eq!(
+            labels(&[(u128::MAX - 1, 3), (u128::MAX, 3), (0, 3), (1, 3)]),
+            vec![(3, format!("0, 1, {}, {}", u128::MAX - 1, u128::MAX))]
+        );
+    }
+
+    #[test]
+    fn switch_branch_labels_target_order() {
+        assert_eq!(
+            labels(&[(0, 7), (1, 2), (2, 7), (3, 4), (4, 2)]),
+            vec![
+                (7, "0, 2".to_string()),
+                (2, "1, 4".to_string()),
+                (4, "3".to_string()),
+            ]
+        );
+        assert_eq!(labels(&[]), vec![]);
+    }
+
+    #[test]
+    fn switch_value_labels() {
+        assert_eq!(switch_value_label(97, true), "'a'");
+        assert_eq!(switch_value_label(0x1F600, true), "'😀'");
+        assert_eq!(switch_value_label(10, true), "'\\n'");
+        assert_eq!(switch_value_label('"' as u128, true), "'\"'");
+        assert_eq!(switch_value_label('\\' as u128, true), "'\\\\'");
+        // surrogates and out-of-range values are not chars
+        assert_eq!(switch_value_label(0xD800, true), "55296");
+        assert_eq!(switch_value_label(0x110000, true), "1114112");
+        assert_eq!(switch_value_label(u128::MAX, true), u128::MAX.to_string());
+        // non-char switches are always numeric
+        assert_eq!(switch_value_label(97, false), "97");
+    }
+
+    fn block(kind: TerminatorKind) -> BasicBlock {
+        BasicBlock {
+            statements: vec![],
+            terminator: Terminator {
+                kind,
+                span: stable_mir::ty::Span::to_val(0),
+            },
+        }
+    }
+
+    #[test]
+    fn compute_predecessors_diamond() {
+        use TerminatorKind::*;
+        // bb0 -> bb1 | bb2 -> bb3, bb3 also reached twice from bb2's switch
+        let blocks = vec![
+            block(SwitchInt {
+                discr: Operand::Copy(Place::from(1)),
+                targets: SwitchTargets::new(vec![(0, 1)], 2),
+            }),
+            block(Goto { target: 3 }),
+            block(SwitchInt {
+                discr: Operand::Copy(Place::from(1)),
+                targets: SwitchTargets::new(vec![(0, 3), (1, 3)], 3),
+            }),
+            block(Return),
+        ];
+        assert_eq!(
+            compute_predecessors(&blocks),
+            vec![vec![], vec![0], vec![0], vec![1, 2]]
+        );
+    }
+
+    #[test]
+    fn escape_dot_char_labels() {
+        let label = |c: char| escape_dot(&switch_value_label(c as u128, true));
+        assert_eq!(label('"'), "'\\\"'");
+        assert_eq!(label('\\'), "'\\\\\\\\'");
+        assert_eq!(label('\n'), "'\\\\n'");
+        assert_eq!(label('a'), "'a'");
+    }
+
+    #[test]
+    fn coverage_labels() {
+        let cases: &[(&str, &str)] = &[
+            ("CounterIncrement(0)", "Coverage counter #0"),
+            ("CounterIncrement(12)", "Coverage counter #12"),
+            ("ExpressionUsed(3)", "Coverage expression #3"),
+            ("BlockMarker(1)", "Coverage block marker #1"),
+            ("SpanMarker", "Coverage SpanMarker"),
+            (
+                "CondBitmapUpdate(index=1, depth=0)",
+                "Coverage CondBitmapUpdate(index=1, depth=0)",
+            ),
+            ("CounterIncrement(x)", "Coverage CounterIncrement(x)"),
+            ("CounterIncrement(1", "Coverage CounterIncrement(1"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                coverage_label(input),
+                *expected,
+                "coverage_label({input:?})"
+            );
+        }
+    }
+
+    #[test]
+    fn unreachable_otherwise_detection() {
+        use stable_mir::mir::{Statement, StatementKind};
+        use TerminatorKind::*;
+        let switch = |otherwise| SwitchTargets::new(vec![(0, 1)], otherwise);
+        let mut unreachable_with_stmt = block(Unreachable);
+        unreachable_with_stmt.statements.push(Statement {
+            kind: StatementKind::Nop,
+            span: stable_mir::ty::Span::to_val(0),
+        });
+        let blocks = vec![
+            block(Return),             // bb0
+            block(Return),             // bb1
+            block(Unreachable),        // bb2: bare
+            unreachable_with_stmt,     // bb3: has a statement
+            block(Goto { target: 0 }), // bb4: not unreachable
+        ];
+        assert!(is_bare_unreachable(&blocks[2]));
+        assert!(!is_bare_unreachable(&blocks[3]));
+        assert!(is_unreachable_otherwise(&blocks, &switch(2)));
+        assert!(!is_unreachable_otherwise(&blocks, &switch(3)));
+        assert!(!is_unreachable_otherwise(&blocks, &switch(4)));
+        assert!(!is_unreachable_otherwise(&blocks, &switch(0)));
+        // out of range targets are not flagged
+        assert!(!is_unreachable_otherwise(&blocks, &switch(9)));
+    }
+
+    #[test]
+    fn preds_labels() {
+        assert_eq!(preds_label(&[]), None);
+        assert_eq!(preds_label(&[1, 2]), Some("preds: bb1, bb2".to_string()));
+    }
+}
@@ -6,6 +6,7 @@ use std::{
     fs::File,
     io,
     iter::Iterator,
+    path::PathBuf,
     str,
     vec::Vec,
 };
@@ -220,6 +221,18 @@ def_env_var!(debug_enabled, DEBUG);
 def_env_var!(link_items_enabled, LINK_ITEMS);
 def_env_var!(link_instance_enabled, LINK_INST);
 
+static RAW_MIR: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Capture the pretty-printed MIR of every function body (`--raw-mir`).
+/// Must be called before the driver runs.
+pub fn enable_raw_mir() {
+    RAW_MIR.store(true, std::sync::atomic::Ordering::Relaxed);
+}
+
+fn raw_mir_enabled() -> bool {
+    RAW_MIR.load(std::sync::atomic::Ordering::Relaxed)
+}
+
 macro_rules! debug_log_println {
     ($($args:tt)*) => {
         #[cfg(feature = "debug_log")]
@@ -301,6 +314,9 @@ pub struct Item {
     pub symbol_name: String,
     pub mono_item_kind: MonoItemKind,
     details: Option<ItemDetails>,
+    /// MIR of the function body as printed by `Body::dump` (`--raw-mir`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_mir: Option<String>,
 }
 
 impl PartialEq for Item {
@@ -348,15 +364,26 @@ fn mk_item(tcx: TyCtxt<'_>, item: MonoItem, sym_name: String) -> Item {
             let id = inst.def.def_id();
             let name = inst.name();
             let internal_id = rustc_internal::internal(tcx, id);
+            let body = inst.body();
+            let raw_mir = if raw_mir_enabled() {
+                body.as_ref().and_then(|b| {
+                    let mut buf = Vec::new();
+                    b.dump(&mut buf, &name).ok()?;
+                    String::from_utf8(buf).ok()
+                })
+            } else {
+                None
+            };
             Item {
                 mono_item: item,
                 symbol_name: sym_name.clone(),
                 mono_item_kind: MonoItemKind::MonoItemFn {
                     name: name.clone(),
                     id,
-                    body: inst.body(),
+                    body,
                 },
                 details: get_item_details(tcx, internal_id, Some(inst)),
+                raw_mir,
             }
         }
         MonoItem::Static(static_def) => {
@@ -380,6 +407,7 @@ fn mk_item(tcx: TyCtxt<'_>, item: MonoItem, sym_name: String) -> Item {
                     allocation: alloc,
                 },
                 details: get_item_details(tcx, internal_id, None),
+                raw_mir: None,
             }
         }
         MonoItem::GlobalAsm(ref asm) => {
@@ -389,6 +417,7 @@ fn mk_item(tcx: TyCtxt<'_>, item: MonoItem, sym_name: String) -> Item {
                 symbol_name: sym_name,
                 mono_item_kind: MonoItemKind::MonoItemGlobalAsm { asm },
                 details: None,
+                raw_mir: None,
             }
         }
     }
@@ -1011,6 +1040,28 @@ fn collect_interned_values<'tcx>(tcx: TyCtxt<'tcx>, items: Vec<&MonoItem>) -> In
 // Collection Transitive Closure
 // =============================
 
+/// Resolve the body of an unevaluated constant to a mono item and its name
+fn resolve_unevaluated_const<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    uconst: &stable_mir::ty::UnevaluatedConst,
+) -> (rustc_middle::mir::mono::MonoItem<'tcx>, String) {
+    let internal_def = rustc_internal::internal(tcx, uconst.def.def_id());
+    let internal_args = rustc_internal::internal(tcx, uconst.args.clone());
+    let maybe_inst = rustc_middle::ty::Instance::try_resolve(
+        tcx,
+        TypingEnv::post_analysis(tcx, internal_def),
+        internal_def,
+        internal_args,
+    );
+    let inst = maybe_inst
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| panic!("Failed to resolve mono item for {:?}", uconst));
+    let internal_mono_item = rustc_middle::mir::mono::MonoItem::Fn(inst);
+    let item_name = mono_item_name_int(tcx, &internal_mono_item);
+    (internal_mono_item, item_name)
+}
+
 struct UnevaluatedConstCollector<'tcx, 'local> {
     tcx: TyCtxt<'tcx>,
     unevaluated_consts: &'local mut HashMap<stable_mir::ty::ConstDef, String>,
@@ -1026,20 +1077,7 @@ impl MirVisitor for UnevaluatedConstCollector<'_, '_> {
         _location: stable_mir::mir::visit::Location,
     ) {
         if let stable_mir::ty::ConstantKind::Unevaluated(uconst) = constant.kind() {
-            let internal_def = rustc_internal::internal(self.tcx, uconst.def.def_id());
-            let internal_args = rustc_internal::internal(self.tcx, uconst.args.clone());
-            let maybe_inst = rustc_middle::ty::Instance::try_resolve(
-                self.tcx,
-                TypingEnv::post_analysis(self.tcx, internal_def),
-                internal_def,
-                internal_args,
-            );
-            let inst = maybe_inst
-                .ok()
-                .flatten()
-                .unwrap_or_else(|| panic!("Failed to resolve mono item for {:?}", uconst));
-            let internal_mono_item = rustc_middle::mir::mono::MonoItem::Fn(inst);
-            let item_name = mono_item_name_int(self.tcx, &internal_mono_item);
+            let (internal_mono_item, item_name) = resolve_unevaluated_const(self.tcx, uconst);
             if !(self.processed_items.contains_key(&item_name)
                 || self.pending_items.contains_key(&item_name)
                 || self.current_item == hash(&item_name))
@@ -1099,6 +1137,259 @@ fn collect_unevaluated_constant_items(
     )
 }
 
+// Reachability filtering
+// ======================
+
+static REACHABLE_ROOTS: std::sync::OnceLock<Vec<String>> = std::sync::OnceLock::new();
+
+/// Restrict all output to the functions reachable from the given root symbols
+/// (`--reachable-from`). Must be called before the driver runs.
+pub fn set_reachable_roots(roots: Vec<String>) {
+    REACHABLE_ROOTS
+        .set(roots)
+        .expect("Reachability roots set more than once");
+}
+
+struct ReachabilityCollector<'tcx, 'local> {
+    tcx: TyCtxt<'tcx>,
+    locals: &'local [LocalDecl],
+    callees: &'local mut HashSet<String>,
+    incomplete: &'local mut bool,
+}
+
+impl ReachabilityCollector<'_, '_> {
+    fn add_fn_ty(&mut self, ty: stable_mir::ty::Ty) {
+        for direct_call in [true, false] {
+            if let Some(inst) = fn_inst_for_ty(ty, direct_call) {
+                // dynamic dispatch has no body, the implementations are kept
+                // where the receiver is unsized to a trait object (`add_vtable`)
+                if !matches!(inst.kind, InstanceKind::Virtual { .. }) {
+                    self.callees.insert(inst.mangled_name());
+                }
+            }
+        }
+    }
+
+    fn add_alloc(&mut self, id: AllocId, seen: &mut HashSet<AllocId>) {
+        if !seen.insert(id) {
+            return;
+        }
+        match GlobalAlloc::from(id) {
+            GlobalAlloc::Function(inst) => {
+                self.callees.insert(inst.mangled_name());
+            }
+            GlobalAlloc::Memory(alloc) => {
+                for (_, prov) in alloc.provenance.ptrs.iter() {
+                    self.add_alloc(prov.0, seen);
+                }
+            }
+            // statics are emitted regardless of reachability. Their initializers
+            // (which may be foreign) are not evaluated.
+            GlobalAlloc::Static(_) => {}
+            vtable @ GlobalAlloc::VTable(..) => {
+                if let Some(entries) = vtable.vtable_allocation() {
+                    self.add_alloc(entries, seen);
+                }
+            }
+        }
+    }
+
+    /// Keep the methods (and drop glue) of `ty` that a vtable for the trait
+    /// object type `dyn_ty` would contain
+    fn add_vtable(&mut self, ty: stable_mir::ty::Ty, dyn_ty: stable_mir::ty::Ty) {
+        let dyn_kind = dyn_ty.kind();
+        if !dyn_kind.is_trait() {
+            return;
+        }
+        let vtable = GlobalAlloc::VTable(ty, dyn_kind.trait_principal());
+        if let Some(entries) = vtable.vtable_allocation() {
+            self.add_alloc(entries, &mut HashSet::new());
+        }
+    }
+}
+
+impl MirVisitor for ReachabilityCollector<'_, '_> {
+    fn visit_rvalue(&mut self, rvalue: &Rvalue, loc: stable_mir::mir::visit::Location) {
+        use stable_mir::mir::{CastKind, PointerCoercion};
+        if let Rvalue::Cast(CastKind::PointerCoercion(PointerCoercion::Unsize), op, target) = rvalue
+        {
+            let pointee = |ty: stable_mir::ty::Ty| ty.kind().builtin_deref(true).map(|t| t.ty);
+            if let (Some(src), Some(dst)) =
+                (op.ty(self.locals).ok().and_then(pointee), pointee(*target))
+            {
+                self.add_vtable(src, dst);
+            }
+        }
+        self.super_rvalue(rvalue, loc);
+    }
+
+    fn visit_terminator(&mut self, term: &Terminator, loc: stable_mir::mir::visit::Location) {
+        use stable_mir::mir::Operand;
+        match &term.kind {
+            TerminatorKind::Call {
+                func: Operand::Copy(_) | Operand::Move(_),
+                ..
+            } => *self.incomplete = true,
+            TerminatorKind::Drop { place, .. } => {
+                if let Ok(drop_ty) = place.ty(self.locals) {
+                    let inst = Instance::resolve_drop_in_place(drop_ty);
+                    self.callees.insert(inst.mangled_name());
+                }
+            }
+            _ => {}
+        }
+        self.super_terminator(term, loc);
+    }
+
+    fn visit_mir_const(
+        &mut self,
+        constant: &stable_mir::ty::MirConst,
+        loc: stable_mir::mir::visit::Location,
+    ) {
+        use stable_mir::ty::ConstantKind;
+        match constant.kind() {
+            // direct calls, closures and function items passed as values
+            ConstantKind::ZeroSized if constant.ty().kind().fn_def().is_some() => {
+                self.add_fn_ty(constant.ty())
+            }
+            ConstantKind::Allocated(alloc) => {
+                let mut seen = HashSet::new();
+                for (_, prov) in alloc.provenance.ptrs.iter() {
+                    self.add_alloc(prov.0, &mut seen);
+                }
+            }
+            // const bodies were added as items by `collect_unevaluated_constant_items`
+            ConstantKind::Unevaluated(uconst) => {
+                self.callees
+                    .insert(resolve_unevaluated_const(self.tcx, uconst).1);
+            }
+            _ => {}
+        }
+        self.super_mir_const(constant, loc);
+    }
+}
+
+/// Find the function items a root specification refers to: an exact symbol
+/// or path match, or else an unambiguous match on the last path segment
+fn find_roots<'a>(items: &'a [Item], root: &str) -> Result<Vec<&'a Item>, String> {
+    let fn_name = |i: &'a Item| match &i.mono_item_kind {
+        MonoItemKind::MonoItemFn { name, .. } => Some(name.as_str()),
+        _ => None,
+    };
+    let short_name = |name: &'a str| name.rsplit("::").next().unwrap_or(name);
+    let exact: Vec<&Item> = items
+        .iter()
+        .filter(|i| i.symbol_name == root || fn_name(i) == Some(root))
+        .collect();
+    if !exact.is_empty() {
+        return Ok(exact);
+    }
+    let short: Vec<&Item> = items
+        .iter()
+        .filter(|i| fn_name(i).map(short_name) == Some(root))
+        .collect();
+    match short.len() {
+        1 => Ok(short),
+        0 => {
+            // close matches: a substring, or a short name within a small edit distance
+            let needle = root.to_lowercase();
+            let max_distance = std::cmp::max(root.len() / 3, 1);
+            let mut suggestions: Vec<&str> = items
+                .iter()
+                .filter_map(fn_name)
+                .filter(|n| {
+                    n.to_lowercase().contains(&needle)
+                        || rustc_span::edit_distance::edit_distance(
+                            &short_name(n).to_lowercase(),
+                            &needle,
+                            max_distance,
+                        )
+                        .is_some()
+                })
+                .collect();
+            suggestions.sort_unstable();
+            suggestions.dedup();
+            suggestions.truncate(5);
+            if suggestions.is_empty() {
+                Err(format!("no function matches `{}`", root))
+            } else {
+                Err(format!(
+                    "no function matches `{}`, did you mean: {}",
+                    root,
+                    suggestions.join(", ")
+                ))
+            }
+        }
+        // names of distinct instances can coincide, list their symbols to pick from
+        _ => Err(format!(
+            "`{}` is ambiguous, candidates: {}",
+            root,
+            short
+                .iter()
+                .map(|i| format!("{} ({})", fn_name(i).unwrap_or_default(), i.symbol_name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Keep only the function items reachable from the `--reachable-from` roots,
+/// following direct calls, drop glue, function pointers in constants, vtables
+/// of trait object coercions, and unevaluated constants. Statics and global asm
+/// are kept unconditionally.
+fn filter_reachable(tcx: TyCtxt<'_>, items: Vec<Item>, roots: &[String]) -> Vec<Item> {
+    let mut worklist: Vec<String> = vec![];
+    for root in roots {
+        let found = find_roots(&items, root)
+            .unwrap_or_else(|msg| tcx.dcx().fatal(format!("--reachable-from: {}", msg)));
+        worklist.extend(found.iter().map(|i| i.symbol_name.clone()));
+    }
+
+    let by_name: HashMap<&str, &Item> = items.iter().map(|i| (i.symbol_name.as_str(), i)).collect();
+    let mut reachable: HashSet<String> = HashSet::new();
+    let mut incomplete = false;
+    while let Some(name) = worklist.pop() {
+        if !reachable.insert(name.clone()) {
+            continue;
+        }
+        let Some(MonoItemKind::MonoItemFn {
+            body: Some(body), ..
+        }) = by_name.get(name.as_str()).map(|i| &i.mono_item_kind)
+        else {
+            continue;
+        };
+        let mut callees = HashSet::new();
+        ReachabilityCollector {
+            tcx,
+            locals: body.locals(),
+            callees: &mut callees,
+            incomplete: &mut incomplete,
+        }
+        .visit_body(body);
+        worklist.extend(callees.into_iter().filter(|c| !reachable.contains(c)));
+    }
+
+    let is_fn = |i: &Item| matches!(i.mono_item_kind, MonoItemKind::MonoItemFn { .. });
+    let total = items.iter().filter(|i| is_fn(i)).count();
+    let kept: Vec<Item> = items
+        .into_iter()
+        .filter(|i| !is_fn(i) || reachable.contains(&i.symbol_name))
+        .collect();
+    let kept_fns = kept.iter().filter(|i| is_fn(i)).count();
+    eprintln!(
+        "--reachable-from: kept {} of {} functions ({} pruned)",
+        kept_fns,
+        total,
+        total - kept_fns
+    );
+    if incomplete {
+        eprintln!(
+            "warning: --reachable-from: calls through function pointers could not be resolved, reachability may be incomplete"
+        );
+    }
+    kept
+}
+
 // Core item collection logic
 // ==========================
 
@@ -1376,7 +1667,12 @@ pub fn collect_smir(tcx: TyCtxt<'_>) -> SmirJson {
     let local_crate = stable_mir::local_crate();
     let items = collect_items(tcx);
     let items_clone = items.clone();
-    let (unevaluated_consts, mut items) = collect_unevaluated_constant_items(tcx, items);
+    let (mut unevaluated_consts, mut items) = collect_unevaluated_constant_items(tcx, items);
+    if let Some(roots) = REACHABLE_ROOTS.get() {
+        items = filter_reachable(tcx, items, roots);
+        let kept: HashSet<&str> = items.iter().map(|i| i.symbol_name.as_str()).collect();
+        unevaluated_consts.retain(|_, item_name| kept.contains(item_name.as_str()));
+    }
     let (calls_map, visited_allocs, visited_tys, span_map) =
         collect_interned_values(tcx, items.iter().map(|i| &i.mono_item).collect::<Vec<_>>());
 
@@ -1454,20 +1750,44 @@ pub fn collect_smir(tcx: TyCtxt<'_>) -> SmirJson {
     }
 }
 
-pub fn emit_smir(tcx: TyCtxt<'_>) {
-    let smir_json =
-        serde_json::to_string(&collect_smir(tcx)).expect("serde_json failed to write result");
+/// Destination for an emitter's output, as resolved from the rustc options
+pub enum OutTarget {
+    Stdout,
+    File(PathBuf),
+}
 
+/// Resolve where an emitter writing files with the given extension should put
+/// its output. `-o <path>` and `--out-dir` determine the file stem and
+/// directory, `-o -` selects stdout. All emitters go through this so that they
+/// agree on file placement.
+pub fn resolve_output(tcx: TyCtxt<'_>, extension: &str) -> OutTarget {
     match tcx.output_filenames(()).path(OutputType::Mir) {
-        OutFileName::Stdout => {
-            write!(&io::stdout(), "{}", smir_json).expect("Failed to write smir.json");
+        OutFileName::Stdout => OutTarget::Stdout,
+        OutFileName::Real(path) => OutTarget::File(path.with_extension(extension)),
+    }
+}
+
+/// Write textual emitter output to the destination given by `resolve_output`
+pub fn write_output(tcx: TyCtxt<'_>, extension: &str, contents: &str) {
+    match resolve_output(tcx, extension) {
+        OutTarget::Stdout => {
+            write!(io::stdout(), "{}", contents)
+                .unwrap_or_else(|e| panic!("Failed to write {} to stdout: {}", extension, e));
         }
-        OutFileName::Real(path) => {
+        OutTarget::File(path) => {
             let mut b = io::BufWriter::new(
-                File::create(path.with_extension("smir.json"))
-                    .expect("Failed to create {path}.smir.json output file"),
+                File::create(&path)
+                    .unwrap_or_else(|e| panic!("Failed to create {}: {}", path.display(), e)),
             );
-            write!(b, "{}", smir_json).expect("Failed to write smir.json");
+            write!(b, "{}", contents)
+                .unwrap_or_else(|e| panic!("Failed to write {}: {}", path.display(), e));
         }
     }
 }
+
+pub fn emit_smir(tcx: TyCtxt<'_>) {
+    let smir_json =
+        serde_json::to_string(&collect_smir(tcx)).expect("serde_json failed to write result");
+
+    write_output(tcx, "smir.json", &smir_json);
+}
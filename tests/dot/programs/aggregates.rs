@@ -0,0 +1,27 @@
+// Aggregate rvalues in constructor syntax: struct, enum variant, tuple, array
+
+struct Limits {
+    count: u32,
+    limit: i64,
+}
+
+struct Pair(u8, u8);
+
+#[inline(never)]
+fn build(n: u32) -> (Limits, Option<u32>, Pair, (u32, bool), [u32; 3]) {
+    (
+        Limits {
+            count: n,
+            limit: 3,
+        },
+        Some(n),
+        Pair(1, 2),
+        (n, true),
+        [n, n, n],
+    )
+}
+
+fn main() {
+    let (l, o, p, t, a) = build(std::hint::black_box(7));
+    assert!(l.count + l.limit as u32 + o.unwrap() + p.0 as u32 + p.1 as u32 + t.0 + a[2] > 0);
+}
@@ -0,0 +1,51 @@
+// Cast rvalues with their target type and annotations: a numeric `as` cast,
+// unsizing to `&dyn Trait` and to a slice, pointer/address casts, a transmute
+
+trait Shape {
+    fn area(&self) -> u32;
+}
+
+struct Square(u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+#[inline(never)]
+fn widen(x: u8) -> u64 {
+    x as u64
+}
+
+#[inline(never)]
+fn as_shape(s: &Square) -> &dyn Shape {
+    s
+}
+
+#[inline(never)]
+fn as_slice(a: &[u8; 4]) -> &[u8] {
+    a
+}
+
+#[inline(never)]
+fn addr_roundtrip(p: *const u8) -> *const u8 {
+    let addr = p as usize;
+    addr as *const u8
+}
+
+#[inline(never)]
+fn to_bits(f: f32) -> u32 {
+    unsafe { std::mem::transmute::<f32, u32>(f) }
+}
+
+fn main() {
+    let sq = Square(3);
+    let bytes = [1u8, 2, 3, 4];
+    let total = widen(7)
+        + as_shape(&sq).area() as u64
+        + as_slice(&bytes).len() as u64
+        + unsafe { *addr_roundtrip(bytes.as_ptr()) } as u64
+        + to_bits(1.0) as u64;
+    assert!(total > 0);
+}
@@ -0,0 +1,15 @@
+// Switches on `char` label their edges with escaped character literals
+
+#[inline(never)]
+fn classify(c: char) -> u8 {
+    match c {
+        '"' => 1,
+        '\\' => 2,
+        '\n' => 3,
+        _ => 0,
+    }
+}
+
+fn main() {
+    assert!(classify(std::hint::black_box('x')) == 0);
+}
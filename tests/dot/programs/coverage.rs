@@ -0,0 +1,16 @@
+// Coverage statements inserted by `-Cinstrument-coverage` (see coverage.flags)
+
+#[inline(never)]
+fn sign(x: i32) -> i32 {
+    if x > 0 {
+        1
+    } else if x < 0 {
+        -1
+    } else {
+        0
+    }
+}
+
+fn main() {
+    assert!(sign(std::hint::black_box(5)) == 1);
+}
@@ -0,0 +1,39 @@
+#!/usr/bin/env bash
+# Runs the programs in `programs/` with `--dot` and checks that every line of
+# `<program>.dot.expected` occurs verbatim in the generated dot file. Only
+# label fragments are pinned, node names and alloc ids are not stable.
+# Extra rustc flags for a program can be given in `<program>.flags`.
+#
+# usage: run_dot_tests.sh [SMIR command]
+
+set -u
+
+SMIR=${1:-"cargo run --"}
+DIR=$(cd "$(dirname "$0")" && pwd)
+OUT=$(mktemp -d)
+trap 'rm -rf "$OUT"' EXIT
+
+errors=""
+for rust in "$DIR"/programs/*.rs; do
+    name=$(basename "$rust" .rs)
+    echo "$rust"
+    flags=""
+    if [ -f "$DIR/programs/$name.flags" ]; then
+        flags=$(cat "$DIR/programs/$name.flags")
+    fi
+    if ! $SMIR --dot -Zno-codegen $flags --out-dir "$OUT" "$rust"; then
+        errors="$errors\n$rust: conversion failed"
+        continue
+    fi
+    while IFS= read -r fragment; do
+        if ! grep -qF -- "$fragment" "$OUT/$name.smir.dot"; then
+            echo "missing: $fragment"
+            errors="$errors\n$rust: missing label fragment"
+        fi
+    done < "$DIR/programs/$name.dot.expected"
+done
+
+if [ -n "$errors" ]; then
+    echo -e "===============\nFAILING TESTS:$errors"
+    exit 1
+fi
@@ -0,0 +1,3 @@
+// Any program will do: the output tests only check where files are written.
+
+fn main() {}
@@ -0,0 +1,65 @@
+#!/usr/bin/env bash
+# Runs each emitter (--json, --dot, --d2) with the output options rustc knows
+# (none, `--out-dir`, `-o <path>`, `-o -`) and checks where the output lands:
+# exactly the expected file is created, or nothing is and stdout has the data.
+#
+# usage: run_output_tests.sh [SMIR command]
+
+set -u
+
+DIR=$(cd "$(dirname "$0")" && pwd)
+ROOT=$(cd "$DIR/../.." && pwd)
+if [ $# -ge 1 ]; then
+    SMIR=$1
+else
+    # the default output location is the working directory, so the tool runs
+    # outside the repository and needs the toolchain named explicitly
+    SMIR="cargo run -q --manifest-path $ROOT/Cargo.toml --"
+    RUSTUP_TOOLCHAIN=$(sed -n 's/^channel = "\(.*\)"/\1/p' "$ROOT/rust-toolchain.toml")
+    export RUSTUP_TOOLCHAIN
+fi
+PROGRAM="$DIR/programs/minimal.rs"
+WORK=$(mktemp -d)
+trap 'rm -rf "$WORK"' EXIT
+
+errors=""
+
+# check <emitter> <extension> <expected file, or - for stdout> <options...>
+check() {
+    local emitter=$1 ext=$2 expected=$3
+    shift 3
+    local case="$emitter $*"
+    echo "$case"
+    rm -rf "$WORK/run" && mkdir -p "$WORK/run/out" "$WORK/run/sub"
+    if ! (cd "$WORK/run" && $SMIR "$emitter" -Zno-codegen "$@" "$PROGRAM" > "$WORK/stdout"); then
+        errors="$errors\n$case: conversion failed"
+        return
+    fi
+    local written
+    written=$(cd "$WORK/run" && find . -type f | sed 's|^\./||' | sort)
+    if [ "$expected" = "-" ]; then
+        if [ -n "$written" ]; then
+            errors="$errors\n$case: wrote $written instead of stdout"
+        elif ! grep -q . "$WORK/stdout"; then
+            errors="$errors\n$case: nothing on stdout"
+        fi
+    elif [ "$written" != "$expected" ]; then
+        errors="$errors\n$case: expected $expected, got ${written:-nothing}"
+    elif [ ! -s "$WORK/run/$expected" ]; then
+        errors="$errors\n$case: $expected is empty"
+    fi
+}
+
+for emitter_ext in "--json json" "--dot dot" "--d2 d2"; do
+    set -- $emitter_ext
+    emitter=$1 ext=$2
+    check "$emitter" "$ext" "minimal.smir.$ext"
+    check "$emitter" "$ext" "out/minimal.smir.$ext" --out-dir out
+    check "$emitter" "$ext" "sub/renamed.smir.$ext" -o sub/renamed
+    check "$emitter" "$ext" "-" -o -
+done
+
+if [ -n "$errors" ]; then
+    echo -e "===============\nFAILING TESTS:$errors"
+    exit 1
+fi
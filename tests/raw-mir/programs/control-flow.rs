@@ -0,0 +1,28 @@
+// `--raw-mir` captures the MIR text of every function body. Branches, a loop,
+// a closure and a panic path give bodies with various block counts.
+
+#[inline(never)]
+fn collatz(mut n: u64) -> u32 {
+    let mut steps = 0;
+    while n != 1 {
+        n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        steps += 1;
+    }
+    steps
+}
+
+#[inline(never)]
+fn describe(x: Option<u8>) -> &'static str {
+    match x {
+        Some(0) => "zero",
+        Some(_) => "some",
+        None => "none",
+    }
+}
+
+fn main() {
+    let add = |a: u32, b: u32| a + b;
+    let v = vec![1u64, 6, 7];
+    let total: u32 = v.iter().map(|&n| collatz(n)).fold(0, add);
+    assert!(describe(Some(total as u8)) == "some");
+}
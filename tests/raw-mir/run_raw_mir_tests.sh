@@ -0,0 +1,56 @@
+#!/usr/bin/env bash
+# Runs the programs in `programs/` with `--raw-mir` and checks that every
+# function item with a body carries its MIR text, that the text read back
+# from the JSON shows one `bbN: {` header per block of `body.blocks`, and that
+# the field is absent without the flag.
+#
+# usage: run_raw_mir_tests.sh [SMIR command]
+
+set -u
+
+SMIR=${1:-"cargo run --"}
+DIR=$(cd "$(dirname "$0")" && pwd)
+OUT=$(mktemp -d)
+trap 'rm -rf "$OUT"' EXIT
+
+# names of function items whose raw MIR is missing or disagrees with the body
+mismatches='
+  .items[]
+  | select(.mono_item_kind.MonoItemFn.body != null)
+  | select(
+      (.raw_mir | type) != "string"
+      or ([.raw_mir | scan("(?m)^    bb[0-9]+: \\{")] | length)
+         != (.mono_item_kind.MonoItemFn.body.blocks | length))
+  | .mono_item_kind.MonoItemFn.name'
+
+errors=""
+for rust in "$DIR"/programs/*.rs; do
+    name=$(basename "$rust" .rs)
+    json="$OUT/$name.smir.json"
+    echo "$rust"
+    if ! $SMIR --json --raw-mir -Zno-codegen --out-dir "$OUT" "$rust"; then
+        errors="$errors\n$rust: conversion failed"
+        continue
+    fi
+    bad=$(jq -r "$mismatches" "$json")
+    if [ -n "$bad" ]; then
+        echo "block count mismatch: $bad"
+        errors="$errors\n$rust: raw MIR does not match body"
+    fi
+    if [ "$(jq '[.items[].mono_item_kind.MonoItemFn.body | select(. != null)] | length' "$json")" = 0 ]; then
+        errors="$errors\n$rust: no function bodies"
+    fi
+
+    if ! $SMIR --json -Zno-codegen --out-dir "$OUT" "$rust"; then
+        errors="$errors\n$rust: conversion failed"
+        continue
+    fi
+    if [ "$(jq '[.items[] | select(has("raw_mir"))] | length' "$json")" != 0 ]; then
+        errors="$errors\n$rust: raw_mir emitted without --raw-mir"
+    fi
+done
+
+if [ -n "$errors" ]; then
+    echo -e "===============\nFAILING TESTS:$errors"
+    exit 1
+fi
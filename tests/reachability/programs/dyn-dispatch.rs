@@ -0,0 +1,46 @@
+// Calls through trait objects: `--reachable-from main` must keep the methods
+// of the types that are unsized to `dyn Shape` (`Square` behind a reference,
+// `Circle` behind a `Box`), but prune the implementation for `Triangle`,
+// which is only called from the exported but unreachable `never_called`.
+
+trait Shape {
+    fn area(&self) -> u32;
+}
+
+struct Square(u32);
+struct Circle(u32);
+struct Triangle(u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> u32 {
+        3 * self.0 * self.0
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> u32 {
+        self.0 * self.0 / 2
+    }
+}
+
+#[inline(never)]
+fn total(shapes: &[&dyn Shape]) -> u32 {
+    shapes.iter().map(|s| s.area()).sum()
+}
+
+#[no_mangle]
+pub fn never_called() -> u32 {
+    let t = Triangle(std::hint::black_box(4));
+    total(&[&t])
+}
+
+fn main() {
+    let boxed: Box<dyn Shape> = Box::new(Circle(1));
+    assert!(total(&[&Square(2), boxed.as_ref()]) == 7);
+}
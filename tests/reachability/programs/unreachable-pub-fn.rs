@@ -0,0 +1,34 @@
+// `--reachable-from main` must keep `main`, `used`, `helper`, `limit` and
+// the allocation of the static `COUNTER`, but prune `never_called`, which is
+// exported (and therefore a mono item) but never called.
+
+static COUNTER: u32 = 7;
+
+trait Limit {
+    const N: u32;
+}
+
+impl Limit for u8 {
+    const N: u32 = 3;
+}
+
+fn limit<T: Limit>() -> u32 {
+    T::N
+}
+
+fn helper(x: u32) -> u32 {
+    x + *std::hint::black_box(&COUNTER)
+}
+
+fn used() -> u32 {
+    helper(limit::<u8>())
+}
+
+#[no_mangle]
+pub fn never_called() -> u32 {
+    used() * 2
+}
+
+fn main() {
+    assert!(used() == 10);
+}
@@ -0,0 +1,51 @@
+#!/usr/bin/env bash
+# Runs the programs in `programs/` with `--reachable-from main` and compares
+# the kept items and allocation kinds against `<program>.reachable.expected`.
+#
+# usage: run_reachability_tests.sh [SMIR command] [--bless]
+
+set -u
+
+SMIR=${1:-"cargo run --"}
+BLESS=${2:-}
+DIR=$(cd "$(dirname "$0")" && pwd)
+OUT=$(mktemp -d)
+trap 'rm -rf "$OUT"' EXIT
+
+summarise='
+  ([.items[].mono_item_kind
+    | (keys[0]) + " " + (.MonoItemFn.name // .MonoItemStatic.name // "<asm>")]
+   + [.allocs[].global_alloc | "alloc " + keys[0]])
+  | sort | .[]'
+
+errors=""
+for rust in "$DIR"/programs/*.rs; do
+    name=$(basename "$rust" .rs)
+    expected="$DIR/programs/$name.reachable.expected"
+    echo "$rust"
+    if ! $SMIR --json --reachable-from main -Zno-codegen --out-dir "$OUT" "$rust"; then
+        errors="$errors\n$rust: conversion failed"
+        continue
+    fi
+    actual=$(jq -r "$summarise" "$OUT/$name.smir.json")
+    if [ "$BLESS" = "--bless" ]; then
+        echo "$actual" > "$expected"
+    elif ! diff <(echo "$actual") "$expected"; then
+        errors="$errors\n$rust: unexpected items"
+    fi
+done
+
+# a misspelt root is reported with a suggestion and a non-zero exit status
+probe="$DIR/programs/unreachable-pub-fn.rs"
+echo "$probe (--reachable-from mian)"
+if stderr=$($SMIR --json --reachable-from mian -Zno-codegen --out-dir "$OUT" "$probe" 2>&1); then
+    errors="$errors\n$probe: unknown root accepted"
+elif ! grep -qF 'did you mean: main' <<< "$stderr"; then
+    echo "$stderr"
+    errors="$errors\n$probe: no suggestion for unknown root"
+fi
+
+if [ -n "$errors" ]; then
+    echo -e "===============\nFAILING TESTS:$errors"
+    exit 1
+fi